@@ -1,4 +1,46 @@
-use std::{error::Error, time::Duration};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Errors that can occur while querying uptime, boot time, load average, or
+/// logged-in sessions.
+#[derive(Debug, Error)]
+pub enum UptimeError {
+    /// The `sysinfo(2)` syscall failed.
+    #[error("sysinfo syscall failed: {0}")]
+    Sysinfo(std::io::Error),
+
+    /// A `sysctl(2)` call failed.
+    #[error("sysctl syscall failed: {0}")]
+    Sysctl(std::io::Error),
+
+    /// A `/proc` file was present but not in the expected format.
+    #[error("unexpected /proc format: {0}")]
+    ProcFormat(String),
+
+    /// A Windows API call failed, carrying the `GetLastError` code.
+    #[error("Windows API error: {0}")]
+    WindowsApi(u32),
+
+    /// The current operating system is not supported by this function.
+    #[error("unsupported operating system")]
+    UnsupportedOs,
+
+    /// The requested data could not be produced even though the platform is supported.
+    #[error("{0}")]
+    Unavailable(String),
+
+    /// A timestamp read from an untrusted source (e.g. a utmp/wtmp file) was out of range.
+    #[error("timestamp out of range")]
+    InvalidTimestamp,
+
+    /// An I/O error occurred, typically while reading a file.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Converting a timestamp to/from `SystemTime` failed.
+    #[error(transparent)]
+    SystemTime(#[from] std::time::SystemTimeError),
+}
 
 /// Returns OS uptime in milliseconds
 ///
@@ -13,21 +55,40 @@ use std::{error::Error, time::Duration};
 /// }
 /// ```
 #[cfg(any(target_os = "linux", target_os = "android"))]
-pub fn get_os_uptime() -> Result<u64, Box<dyn Error>> {
+pub fn get_os_uptime() -> Result<u64, UptimeError> {
+    use std::mem;
+
+    unsafe {
+        let mut info: libc::sysinfo = mem::zeroed();
+        if libc::sysinfo(&mut info) == 0 {
+            return Ok(info.uptime as u64 * 1000);
+        }
+    }
+
+    // sysinfo(2) failed; fall back to /proc/uptime and report whatever it
+    // actually found wrong (missing file, garbled contents) rather than
+    // masking it behind the sysinfo failure.
+    get_os_uptime_from_proc()
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn get_os_uptime_from_proc() -> Result<u64, UptimeError> {
     use std::fs;
 
     let uptime_content = fs::read_to_string("/proc/uptime")?;
     let parts = uptime_content.split_whitespace().collect::<Vec<_>>();
 
     if parts.is_empty() {
-        return Err("Invalid /proc/uptime format".into());
+        return Err(UptimeError::ProcFormat("/proc/uptime is empty".into()));
     }
 
-    let uptime_seconds: f64 = parts[0].parse()?;
+    let uptime_seconds: f64 = parts[0]
+        .parse()
+        .map_err(|_| UptimeError::ProcFormat("/proc/uptime does not start with a number".into()))?;
     Ok((uptime_seconds * 1000.0) as u64)
 }
 #[cfg(target_os = "windows")]
-pub fn get_os_uptime() -> Result<u64, Box<dyn Error>> {
+pub fn get_os_uptime() -> Result<u64, UptimeError> {
     use winapi::um::sysinfoapi::{GetTickCount64, GetLastError};
 
     unsafe {
@@ -35,17 +96,17 @@ pub fn get_os_uptime() -> Result<u64, Box<dyn Error>> {
         if uptime_ms == 0 {
             let error_code = GetLastError();
             if error_code != 0 {
-                return Err(format!("Windows API error: {}", error_code).into());
+                return Err(UptimeError::WindowsApi(error_code));
             }
         }
         Ok(uptime_ms)
     }
 }
 #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
-pub fn get_os_uptime() -> Result<u64, Box<dyn Error>> {
+fn bsd_boot_time() -> Result<libc::timeval, UptimeError> {
     use libc::{sysctl, timeval};
-    use std::mem;
     use std::io;
+    use std::mem;
 
     let mut mib = [libc::CTL_KERN, libc::KERN_BOOTTIME];
     let mut boot_time = timeval { tv_sec: 0, tv_usec: 0 };
@@ -61,13 +122,19 @@ pub fn get_os_uptime() -> Result<u64, Box<dyn Error>> {
             0
         ) != 0
         {
-            return Err(io::Error::last_os_error().into());
+            return Err(UptimeError::Sysctl(io::Error::last_os_error()));
         }
-
-        let now = libc::time(std::ptr::null_mut());
-        let uptime_seconds = now - boot_time.tv_sec;
-        Ok(uptime_seconds as u64 * 1000)
     }
+
+    Ok(boot_time)
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+pub fn get_os_uptime() -> Result<u64, UptimeError> {
+    let boot_time = bsd_boot_time()?;
+    let now = unsafe { libc::time(std::ptr::null_mut()) };
+    let uptime_seconds = now - boot_time.tv_sec;
+    Ok(uptime_seconds as u64 * 1000)
 }
 #[cfg(not(any(
     target_os = "windows",
@@ -77,16 +144,328 @@ pub fn get_os_uptime() -> Result<u64, Box<dyn Error>> {
     target_os = "ios",
     target_os = "freebsd"
 )))]
-pub fn get_os_uptime() -> Result<u64, Box<dyn Error>> {
-    Err("Unsupported operating system".into())
+pub fn get_os_uptime() -> Result<u64, UptimeError> {
+    Err(UptimeError::UnsupportedOs)
 }
 
 /// Returns OS uptime in useful Duration format
-pub fn get_os_uptime_duration() -> Result<Duration, Box<dyn Error>> {
+pub fn get_os_uptime_duration() -> Result<Duration, UptimeError> {
     let ms = get_os_uptime()?;
     Ok(Duration::from_millis(ms))
 }
 
+/// Derives uptime offline from a utmp/wtmp-style dump, rather than the live system.
+///
+/// Scans `path` for fixed-size `utmp` records and uses the timestamp of the
+/// most recent `BOOT_TIME` record to compute `now - boot_time`. Useful for fixture-driven
+/// tests and for post-mortem analysis of a captured `/var/log/wtmp`.
+#[cfg(target_os = "linux")]
+pub fn get_os_uptime_from_file(path: &std::path::Path) -> Result<u64, UptimeError> {
+    use std::fs;
+    use std::mem;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    const BOOT_TIME: i16 = 2;
+
+    let raw = fs::read(path)?;
+    let record_size = mem::size_of::<libc::utmp>();
+
+    let boot_record = raw
+        .chunks_exact(record_size)
+        .map(|chunk| unsafe { std::ptr::read_unaligned(chunk.as_ptr() as *const libc::utmp) })
+        .filter(|record| record.ut_type == BOOT_TIME)
+        .max_by_key(|record| record.ut_tv.tv_sec)
+        .ok_or_else(|| UptimeError::Unavailable("no BOOT_TIME record found in utmp/wtmp file".into()))?;
+
+    let boot_time = UNIX_EPOCH
+        .checked_add(Duration::from_secs(boot_record.ut_tv.tv_sec as u64))
+        .ok_or(UptimeError::InvalidTimestamp)?;
+    let now = SystemTime::now();
+
+    let uptime = now.duration_since(boot_time)?;
+    Ok(uptime.as_millis() as u64)
+}
+
+/// Returns the time the system was booted.
+///
+/// # Example
+///
+/// ```
+/// use system_uptime::get_boot_time;
+///
+/// match get_boot_time() {
+///     Ok(boot_time) => println!("System up since {:?}", boot_time),
+///     Err(e) => eprintln!("Error is {}", e),
+/// }
+/// ```
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get_boot_time() -> Result<std::time::SystemTime, UptimeError> {
+    Ok(std::time::SystemTime::now() - get_os_uptime_duration()?)
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_boot_time() -> Result<std::time::SystemTime, UptimeError> {
+    Ok(std::time::SystemTime::now() - get_os_uptime_duration()?)
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+pub fn get_boot_time() -> Result<std::time::SystemTime, UptimeError> {
+    use std::time::{Duration as StdDuration, UNIX_EPOCH};
+
+    let boot_time = bsd_boot_time()?;
+    Ok(UNIX_EPOCH
+        + StdDuration::from_secs(boot_time.tv_sec as u64)
+        + StdDuration::from_micros(boot_time.tv_usec as u64))
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+)))]
+pub fn get_boot_time() -> Result<std::time::SystemTime, UptimeError> {
+    Err(UptimeError::UnsupportedOs)
+}
+
+/// Returns the time the system was booted, as seconds since the Unix epoch.
+pub fn get_boot_time_secs() -> Result<u64, UptimeError> {
+    let boot_time = get_boot_time()?;
+    let secs = boot_time.duration_since(std::time::UNIX_EPOCH)?.as_secs();
+    Ok(secs)
+}
+
+/// The classic 1/5/15-minute load average figures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadAverage {
+    /// Average load over the last minute.
+    pub one: f64,
+    /// Average load over the last 5 minutes.
+    pub five: f64,
+    /// Average load over the last 15 minutes.
+    pub fifteen: f64,
+}
+
+/// Returns the system's 1/5/15-minute load average.
+///
+/// # Example
+///
+/// ```
+/// use system_uptime::get_load_average;
+///
+/// match get_load_average() {
+///     Ok(load) => println!("load average: {} {} {}", load.one, load.five, load.fifteen),
+///     Err(e) => eprintln!("Error is {}", e),
+/// }
+/// ```
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get_load_average() -> Result<LoadAverage, UptimeError> {
+    use std::fs;
+
+    if let Ok(content) = fs::read_to_string("/proc/loadavg") {
+        let parts = content.split_whitespace().collect::<Vec<_>>();
+        if parts.len() >= 3 {
+            let parse = |s: &str| {
+                s.parse::<f64>()
+                    .map_err(|_| UptimeError::ProcFormat("/proc/loadavg does not start with three numbers".into()))
+            };
+            return Ok(LoadAverage {
+                one: parse(parts[0])?,
+                five: parse(parts[1])?,
+                fifteen: parse(parts[2])?,
+            });
+        }
+    }
+
+    libc_load_average()
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd"))]
+pub fn get_load_average() -> Result<LoadAverage, UptimeError> {
+    libc_load_average()
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+))]
+fn libc_load_average() -> Result<LoadAverage, UptimeError> {
+    let mut loads: [f64; 3] = [0.0; 3];
+
+    let count = unsafe { libc::getloadavg(loads.as_mut_ptr(), 3) };
+    if count != 3 {
+        return Err(UptimeError::Unavailable("getloadavg did not return 3 load figures".into()));
+    }
+
+    Ok(LoadAverage {
+        one: loads[0],
+        five: loads[1],
+        fifteen: loads[2],
+    })
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+)))]
+pub fn get_load_average() -> Result<LoadAverage, UptimeError> {
+    Err(UptimeError::UnsupportedOs)
+}
+
+/// A single logged-in session, as reported by the system's utmp/utmpx database.
+///
+/// This mirrors the information printed by the classic `who`/`uptime -a` tools.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    /// Login name of the user.
+    pub user: String,
+    /// Terminal/line the session is attached to (e.g. `tty1`, `pts/0`).
+    pub line: String,
+    /// Remote host the session originated from, empty for local sessions.
+    pub host: String,
+    /// Time the session was started.
+    pub login_time: std::time::SystemTime,
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "ios", target_os = "freebsd", target_os = "openbsd"))]
+fn c_array_to_string(chars: &[libc::c_char]) -> String {
+    let bytes: Vec<u8> = chars
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Returns every session currently logged in to the system.
+///
+/// # Example
+///
+/// ```
+/// use system_uptime::get_logged_in_users;
+///
+/// match get_logged_in_users() {
+///     Ok(sessions) => println!("{} user(s) logged in", sessions.len()),
+///     Err(e) => eprintln!("Error is {}", e),
+/// }
+/// ```
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+pub fn get_logged_in_users() -> Result<Vec<SessionInfo>, UptimeError> {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    const USER_PROCESS: i16 = 7;
+
+    let mut sessions = Vec::new();
+
+    unsafe {
+        libc::setutxent();
+
+        loop {
+            let entry = libc::getutxent();
+            if entry.is_null() {
+                break;
+            }
+
+            let entry = &*entry;
+            if entry.ut_type != USER_PROCESS {
+                continue;
+            }
+
+            let user = c_array_to_string(&entry.ut_user);
+            if user.is_empty() {
+                continue;
+            }
+
+            let login_time = UNIX_EPOCH
+                + Duration::from_secs(entry.ut_tv.tv_sec as u64)
+                + Duration::from_micros(entry.ut_tv.tv_usec as u64);
+
+            sessions.push(SessionInfo {
+                user,
+                line: c_array_to_string(&entry.ut_line),
+                host: c_array_to_string(&entry.ut_host),
+                login_time,
+            });
+        }
+
+        libc::endutxent();
+    }
+
+    Ok(sessions)
+}
+
+/// OpenBSD has no live `utmpx` database; logged-in sessions live in the older
+/// fixed-layout `utmp` records at `/var/run/utmp`.
+#[cfg(target_os = "openbsd")]
+const OPENBSD_UTMP_PATH: &str = "/var/run/utmp";
+
+#[cfg(target_os = "openbsd")]
+#[repr(C)]
+struct OpenBsdUtmp {
+    ut_line: [libc::c_char; 8],
+    ut_name: [libc::c_char; 8],
+    ut_host: [libc::c_char; 16],
+    ut_time: libc::time_t,
+}
+
+/// Returns every session currently logged in to the system.
+#[cfg(target_os = "openbsd")]
+pub fn get_logged_in_users() -> Result<Vec<SessionInfo>, UptimeError> {
+    use std::fs;
+    use std::mem;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let raw = fs::read(OPENBSD_UTMP_PATH)?;
+    let record_size = mem::size_of::<OpenBsdUtmp>();
+    let mut sessions = Vec::new();
+
+    for chunk in raw.chunks_exact(record_size) {
+        let record = unsafe { &*(chunk.as_ptr() as *const OpenBsdUtmp) };
+
+        let user = c_array_to_string(&record.ut_name);
+        if user.is_empty() {
+            continue;
+        }
+
+        sessions.push(SessionInfo {
+            user,
+            line: c_array_to_string(&record.ut_line),
+            host: c_array_to_string(&record.ut_host),
+            login_time: UNIX_EPOCH + Duration::from_secs(record.ut_time as u64),
+        });
+    }
+
+    Ok(sessions)
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+)))]
+pub fn get_logged_in_users() -> Result<Vec<SessionInfo>, UptimeError> {
+    Err(UptimeError::UnsupportedOs)
+}
+
+/// Returns the number of sessions currently logged in to the system.
+pub fn get_user_count() -> Result<usize, UptimeError> {
+    Ok(get_logged_in_users()?.len())
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -103,4 +482,117 @@ mod tests {
         let duration = get_os_uptime_duration().unwrap();
         assert_eq!(duration.as_millis() as u64, uptime_ms);
     }
+
+    #[test]
+    fn it_boot_time() {
+        let boot_time = get_boot_time();
+        assert!(boot_time.is_ok(), "Failed to get boot time: {:?}", boot_time.err());
+        assert!(boot_time.unwrap() <= std::time::SystemTime::now());
+
+        let boot_time_secs = get_boot_time_secs().unwrap();
+        assert!(boot_time_secs > 0, "Boot time should be greater than 0");
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn it_load_average() {
+        let load = get_load_average();
+        assert!(load.is_ok(), "Failed to get load average: {:?}", load.err());
+
+        let load = load.unwrap();
+        assert!(load.one >= 0.0);
+        assert!(load.five >= 0.0);
+        assert!(load.fifteen >= 0.0);
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn it_load_average_unsupported() {
+        let load = get_load_average();
+        assert!(matches!(load, Err(UptimeError::UnsupportedOs)));
+    }
+
+    #[test]
+    fn it_logged_in_users() {
+        let sessions = get_logged_in_users();
+        assert!(sessions.is_ok(), "Failed to get logged in users: {:?}", sessions.err());
+
+        let count = get_user_count().unwrap();
+        assert_eq!(count, sessions.unwrap().len());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn it_os_uptime_from_file() {
+        use std::mem;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let boot_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+            - 3600;
+
+        let mut record: libc::utmp = unsafe { mem::zeroed() };
+        record.ut_type = 2; // BOOT_TIME
+        record.ut_tv.tv_sec = boot_secs as _;
+
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                &record as *const _ as *const u8,
+                mem::size_of::<libc::utmp>(),
+            )
+        };
+
+        let path = std::env::temp_dir().join("system_uptime_test_wtmp");
+        std::fs::write(&path, bytes).unwrap();
+
+        let uptime = get_os_uptime_from_file(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(uptime.is_ok(), "Failed to get uptime from file: {:?}", uptime.err());
+        let uptime_ms = uptime.unwrap();
+        assert!(uptime_ms >= 3600 * 1000);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn it_os_uptime_from_file_uses_most_recent_boot() {
+        use std::mem;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let now_secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+
+        let make_record = |boot_secs: i64| -> libc::utmp {
+            let mut record: libc::utmp = unsafe { mem::zeroed() };
+            record.ut_type = 2; // BOOT_TIME
+            record.ut_tv.tv_sec = boot_secs as _;
+            record
+        };
+
+        // An older reboot appears earlier in the file, a more recent one later,
+        // as would happen in a wtmp file appended to across multiple boots.
+        let old_boot = make_record(now_secs - 30 * 24 * 3600);
+        let recent_boot = make_record(now_secs - 3600);
+
+        let mut bytes = Vec::new();
+        for record in [old_boot, recent_boot] {
+            bytes.extend_from_slice(unsafe {
+                std::slice::from_raw_parts(
+                    &record as *const _ as *const u8,
+                    mem::size_of::<libc::utmp>(),
+                )
+            });
+        }
+
+        let path = std::env::temp_dir().join("system_uptime_test_wtmp_multi");
+        std::fs::write(&path, &bytes).unwrap();
+
+        let uptime = get_os_uptime_from_file(&path);
+        std::fs::remove_file(&path).ok();
+
+        let uptime_ms = uptime.expect("Failed to get uptime from file");
+        assert!(uptime_ms >= 3600 * 1000, "uptime should be based on the most recent boot, got {}ms", uptime_ms);
+        assert!(uptime_ms < 2 * 3600 * 1000, "uptime should not use the stale boot record, got {}ms", uptime_ms);
+    }
 }